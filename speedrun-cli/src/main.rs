@@ -0,0 +1,298 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde_json::Value;
+use speedrun_client::{CategoryObj, GameResult, RecordCategory, SpeedrunClient};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[macro_use(row)]
+extern crate tabular;
+
+mod splits;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "speedrun-rust-cli", about = "CLI for exploring speedrun.com")]
+struct Opts {
+    #[structopt(subcommand)]
+    cmd: Cmd,
+
+    /// Don't use or write to the on-disk response cache
+    #[structopt(long)]
+    no_cache: bool,
+
+    /// Bypass cached responses and refetch from the API
+    #[structopt(long)]
+    refresh: bool,
+
+    /// Twitch application client id, for resolving VOD metadata (also read from TWITCH_CLIENT_ID)
+    #[cfg(feature = "twitch")]
+    #[structopt(long, env = "TWITCH_CLIENT_ID")]
+    twitch_client_id: Option<String>,
+
+    /// Twitch application client secret, for resolving VOD metadata (also read from TWITCH_CLIENT_SECRET)
+    #[cfg(feature = "twitch")]
+    #[structopt(long, env = "TWITCH_CLIENT_SECRET", hide_env_values = true)]
+    twitch_client_secret: Option<String>,
+}
+
+#[derive(StructOpt, Debug)]
+enum Cmd {
+    Game {
+        /// Game name
+        #[structopt(short, long)]
+        name: String,
+    },
+    Player {
+        /// Player ID
+        #[structopt(short, long)]
+        id: String,
+
+        #[structopt(short, long)]
+        debug: bool,
+    },
+    Splits {
+        /// Path to a LiveSplit `.lss` splits file
+        #[structopt(short, long, parse(from_os_str))]
+        file: PathBuf,
+
+        /// Game name
+        #[structopt(short, long)]
+        game: String,
+
+        /// Category name
+        #[structopt(short, long)]
+        category: String,
+    },
+    /// Show the world-record progression and newly submitted runs for a category,
+    /// based on locally stored leaderboard snapshots
+    #[cfg(feature = "history")]
+    History {
+        /// Game name
+        #[structopt(short, long)]
+        game: String,
+
+        /// Category name
+        #[structopt(short, long)]
+        category: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let opts = Opts::from_args();
+    let client_builder = SpeedrunClient::builder()
+        .no_cache(opts.no_cache)
+        .refresh(opts.refresh);
+
+    #[cfg(feature = "twitch")]
+    let client_builder = match (opts.twitch_client_id, opts.twitch_client_secret) {
+        (Some(client_id), Some(client_secret)) => client_builder.twitch_credentials(client_id, client_secret),
+        _ => client_builder,
+    };
+
+    let client = client_builder.build();
+
+    match opts.cmd {
+        Cmd::Game { name } => {
+            let games = client.games(&name).await?;
+
+            if games.is_empty() {
+                panic!("No games came back with the search of {}", name)
+            }
+
+            let names = &games
+                .iter()
+                .map(|game| game.names.international.clone())
+                .collect::<Vec<Value>>();
+
+            let game_name = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a game:")
+                .default(0)
+                .items(names)
+                .interact()?;
+
+            println!("You selected: {:?}", &games[game_name]);
+
+            let selected_game = &games[game_name];
+            let (categories, mut records) = fetch_game_records(&client, selected_game).await?;
+            records.retain(|record| {
+                let cat = &categories.get(&record.category).unwrap();
+                cat.r#type == "per-game"
+            });
+
+            println!("Runs for {}\n", selected_game.names.international);
+            for category in records {
+                let cat_name = &categories.get(&category.category).unwrap().name;
+                println!("Category: {}", cat_name);
+                println!("{}", category);
+            }
+        }
+        Cmd::Player { id, debug } => {
+            // example player: 0jm34we8
+            let player = client.user(&id).await?;
+            if debug {
+                dbg!(player);
+            }
+        }
+        Cmd::Splits {
+            file,
+            game,
+            category,
+        } => {
+            let pb_seconds = splits::personal_best_seconds(&file)?;
+
+            let games = client.games(&game).await?;
+            let selected_game = games
+                .first()
+                .ok_or_else(|| format!("No games came back with the search of {}", game))?;
+
+            let (categories, records) = fetch_game_records(&client, selected_game).await?;
+            let category_obj = categories
+                .values()
+                .find(|cat| cat.name.eq_ignore_ascii_case(&category))
+                .ok_or_else(|| format!("No category named {} for {}", category, game))?;
+            let record_category = records
+                .iter()
+                .find(|record| record.category == category_obj.id)
+                .ok_or_else(|| format!("No leaderboard for category {}", category))?;
+
+            print_splits_comparison(record_category, pb_seconds);
+        }
+        #[cfg(feature = "history")]
+        Cmd::History { game, category } => {
+            let games = client.games(&game).await?;
+            let selected_game = games
+                .first()
+                .ok_or_else(|| format!("No games came back with the search of {}", game))?;
+
+            let (categories, records) = fetch_game_records(&client, selected_game).await?;
+            let category_obj = categories
+                .values()
+                .find(|cat| cat.name.eq_ignore_ascii_case(&category))
+                .ok_or_else(|| format!("No category named {} for {}", category, game))?;
+            let record_category = records
+                .iter()
+                .find(|record| record.category == category_obj.id)
+                .ok_or_else(|| format!("No leaderboard for category {}", category))?;
+
+            print_history(&client, record_category)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the world-record progression and newly submitted runs for
+/// `record_category`, based on locally stored leaderboard snapshots.
+#[cfg(feature = "history")]
+fn print_history(
+    client: &SpeedrunClient,
+    record_category: &RecordCategory,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let progression = client.wr_progression(&record_category.category)?;
+
+    println!("World record progression for {}\n", record_category.category);
+    if progression.is_empty() {
+        println!("No snapshots recorded yet.");
+    } else {
+        let mut table = tabular::Table::new("{:<}  {:<}  {:<}");
+        table.add_row(row!("Taken at", "Player", "Time"));
+        for change in &progression {
+            table.add_row(row!(
+                change.taken_at,
+                &change.player,
+                format!("{:.3}s", change.time_seconds)
+            ));
+        }
+        print!("{}", table);
+    }
+
+    let new_runs = client.new_runs_since_last_snapshot(&record_category.category)?;
+    if !new_runs.is_empty() {
+        println!("\nNew runs since last snapshot:");
+        for new_run in &new_runs {
+            println!(
+                "  {} - {:.3}s ({})",
+                new_run.player, new_run.time_seconds, new_run.weblink
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches a game's categories and its record leaderboards in one shot.
+async fn fetch_game_records(
+    client: &SpeedrunClient,
+    game: &GameResult,
+) -> Result<(HashMap<String, CategoryObj>, Vec<RecordCategory>), Box<dyn std::error::Error>> {
+    let records_endpoint_uri: String = game
+        .links
+        .iter()
+        .find(|link| link.rel == "records")
+        .unwrap()
+        .uri
+        .clone();
+    let category_endpoint_uri: String = game
+        .links
+        .iter()
+        .find(|link| link.rel == "categories")
+        .unwrap()
+        .uri
+        .clone();
+
+    let categories = client.categories(&category_endpoint_uri).await?;
+    let records = client.game_records(&records_endpoint_uri).await?;
+
+    Ok((categories, records))
+}
+
+/// Prints where `pb_seconds` would rank on `record_category`'s leaderboard,
+/// along with the delta to the WR, the run just ahead, and the next place below.
+fn print_splits_comparison(record_category: &RecordCategory, pb_seconds: f64) {
+    let wr = record_category.runs.first();
+    let mut ahead = None;
+    let mut below = None;
+    for run in &record_category.runs {
+        if run.total_seconds() <= pb_seconds {
+            ahead = Some(run);
+        } else {
+            below = Some(run);
+            break;
+        }
+    }
+
+    let place = match &ahead {
+        Some(ahead) => record_category
+            .runs
+            .iter()
+            .position(|run| run.id == ahead.id)
+            .unwrap()
+            + 2,
+        None => 1,
+    };
+
+    println!(
+        "Your PB of {:.3}s would place #{} on the {} leaderboard\n",
+        pb_seconds, place, record_category.category
+    );
+
+    let mut table = tabular::Table::new("{:<}  {:<}  {:<}");
+    table.add_row(row!("", "Time", "Delta to your PB"));
+    if let Some(wr) = wr {
+        table.add_row(row!("World Record", format!("{:.3}s", wr.total_seconds()), format_delta(wr.total_seconds(), pb_seconds)));
+    }
+    if let Some(ahead) = ahead {
+        table.add_row(row!("Just ahead", format!("{:.3}s", ahead.total_seconds()), format_delta(ahead.total_seconds(), pb_seconds)));
+    }
+    table.add_row(row!("Your PB", format!("{:.3}s", pb_seconds), "-"));
+    if let Some(below) = below {
+        table.add_row(row!("Next place below", format!("{:.3}s", below.total_seconds()), format_delta(below.total_seconds(), pb_seconds)));
+    }
+
+    print!("{}", table);
+}
+
+fn format_delta(other_seconds: f64, pb_seconds: f64) -> String {
+    format!("{:+.3}s", other_seconds - pb_seconds)
+}
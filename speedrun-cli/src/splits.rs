@@ -0,0 +1,114 @@
+//! Parsing of LiveSplit `.lss` splits files, for comparing a runner's
+//! personal best against the fetched leaderboard.
+
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Run {
+    #[serde(rename = "Segments")]
+    segments: Segments,
+}
+
+#[derive(Debug, Deserialize)]
+struct Segments {
+    #[serde(rename = "Segment", default)]
+    segment: Vec<Segment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Segment {
+    #[serde(rename = "SplitTimes")]
+    split_times: SplitTimes,
+}
+
+#[derive(Debug, Deserialize)]
+struct SplitTimes {
+    #[serde(rename = "SplitTime", default)]
+    split_time: Vec<SplitTime>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SplitTime {
+    name: String,
+    #[serde(rename = "RealTime")]
+    real_time: Option<String>,
+}
+
+/// Reads a LiveSplit `.lss` file and returns the runner's full-run personal
+/// best, in total seconds, taken from the final segment's `RealTime` PB.
+pub fn personal_best_seconds(path: &Path) -> Result<f64, Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)?;
+    let run: Run = quick_xml::de::from_str(&contents)?;
+
+    let final_segment = run
+        .segments
+        .segment
+        .last()
+        .ok_or("splits file has no segments")?;
+
+    let pb_real_time = final_segment
+        .split_times
+        .split_time
+        .iter()
+        .find(|split| split.name == "Personal Best")
+        .and_then(|split| split.real_time.as_deref())
+        .ok_or("final segment has no Personal Best split time")?;
+
+    parse_hms_seconds(pb_real_time)
+}
+
+/// Parses a LiveSplit `HH:MM:SS.fffffff` real time into total seconds.
+fn parse_hms_seconds(raw: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    let parts: Vec<&str> = raw.split(':').collect();
+    let &[hours, minutes, seconds] = parts.as_slice() else {
+        return Err(format!("unexpected RealTime format: {}", raw).into());
+    };
+
+    Ok(hours.parse::<f64>()? * 3600.0 + minutes.parse::<f64>()? * 60.0 + seconds.parse::<f64>()?)
+}
+
+#[test]
+fn parse_hms_seconds_parses_fractional_time() {
+    assert_eq!(parse_hms_seconds("00:01:02.5000000").unwrap(), 62.5);
+    assert_eq!(parse_hms_seconds("01:00:00.0000000").unwrap(), 3600.0);
+    assert_eq!(parse_hms_seconds("00:00:00.2500000").unwrap(), 0.25);
+}
+
+#[test]
+fn parse_hms_seconds_rejects_unexpected_shape() {
+    assert!(parse_hms_seconds("01:02").is_err());
+    assert!(parse_hms_seconds("not a time").is_err());
+}
+
+#[test]
+fn personal_best_seconds_reads_final_segment_pb() {
+    let lss = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Run>
+  <Segments>
+    <Segment>
+      <SplitTimes>
+        <SplitTime name="Personal Best">
+          <RealTime>00:00:30.0000000</RealTime>
+        </SplitTime>
+      </SplitTimes>
+    </Segment>
+    <Segment>
+      <SplitTimes>
+        <SplitTime name="Personal Best">
+          <RealTime>00:01:45.2500000</RealTime>
+        </SplitTime>
+      </SplitTimes>
+    </Segment>
+  </Segments>
+</Run>
+"#;
+    let dir = std::env::temp_dir();
+    let path = dir.join("speedrun-rust-cli-test-personal-best.lss");
+    std::fs::write(&path, lss).unwrap();
+
+    let seconds = personal_best_seconds(&path).unwrap();
+
+    std::fs::remove_file(&path).ok();
+    assert_eq!(seconds, 105.25);
+}
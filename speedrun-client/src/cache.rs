@@ -0,0 +1,139 @@
+//! A small on-disk JSON response cache, keyed by request URL.
+//!
+//! Responses are kept in a single `cache.json` file under the platform
+//! cache dir so repeated lookups (e.g. re-running to pick a different
+//! category) don't re-hit the speedrun.com API.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ResponseCache {
+    entries: HashMap<String, CacheEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: u64,
+    ttl_secs: u64,
+    value: Value,
+}
+
+impl ResponseCache {
+    /// Loads the cache from `path`, starting empty if it doesn't exist or is invalid.
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        ResponseCache { entries, path }
+    }
+
+    /// Returns the cached value for `key`, if present and not yet expired.
+    pub(crate) fn get(&self, key: &str) -> Option<Value> {
+        let entry = self.entries.get(key)?;
+        if now_secs().saturating_sub(entry.fetched_at) < entry.ttl_secs {
+            Some(entry.value.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Stores `value` under `key` with the given TTL and persists to disk.
+    pub(crate) fn put(&mut self, key: String, value: Value, ttl_secs: u64) {
+        self.entries.insert(
+            key,
+            CacheEntry {
+                fetched_at: now_secs(),
+                ttl_secs,
+                value,
+            },
+        );
+
+        if let Err(err) = self.save() {
+            log_save_error(&err);
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_vec(&self.entries)?)
+    }
+}
+
+fn log_save_error(err: &std::io::Error) {
+    eprintln!("warning: failed to write speedrun-rust-cli cache: {}", err);
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The default cache file location: `<platform cache dir>/speedrun-rust-cli/cache.json`.
+pub(crate) fn default_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("speedrun-rust-cli").join("cache.json"))
+}
+
+#[cfg(test)]
+fn test_cache_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("speedrun-rust-cli-test-{}-{}.json", name, std::process::id()))
+}
+
+#[test]
+fn get_returns_none_for_missing_key() {
+    let cache = ResponseCache::load(test_cache_path("missing-key"));
+    assert!(cache.get("https://speedrun.com/api/v1/games").is_none());
+}
+
+#[test]
+fn get_returns_value_before_ttl_expires() {
+    let path = test_cache_path("not-expired");
+    let mut cache = ResponseCache::load(path.clone());
+    cache.put("key".to_string(), serde_json::json!({"id": "abc"}), 60);
+
+    assert_eq!(cache.get("key"), Some(serde_json::json!({"id": "abc"})));
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn get_returns_none_after_ttl_expires() {
+    let path = test_cache_path("expired");
+    let mut cache = ResponseCache::load(path.clone());
+    cache.entries.insert(
+        "key".to_string(),
+        CacheEntry {
+            fetched_at: now_secs().saturating_sub(120),
+            ttl_secs: 60,
+            value: serde_json::json!({"id": "abc"}),
+        },
+    );
+
+    assert!(cache.get("key").is_none());
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn load_round_trips_through_disk() {
+    let path = test_cache_path("round-trip");
+    let mut cache = ResponseCache::load(path.clone());
+    cache.put("key".to_string(), serde_json::json!("value"), 60);
+
+    let reloaded = ResponseCache::load(path.clone());
+    assert_eq!(reloaded.get("key"), Some(serde_json::json!("value")));
+
+    fs::remove_file(&path).ok();
+}
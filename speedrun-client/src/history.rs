@@ -0,0 +1,265 @@
+//! SQLite-backed snapshot history of leaderboard pulls, behind the `history` feature.
+//!
+//! Each `game_records` fetch persists a timestamped snapshot of every
+//! `RecordCategory`'s runs, so [`crate::SpeedrunClient::wr_progression`] and
+//! [`crate::SpeedrunClient::new_runs_since_last_snapshot`] can diff
+//! consecutive snapshots to show how a leaderboard evolved.
+
+use crate::RecordCategory;
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    id TEXT PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS categories (
+    id TEXT PRIMARY KEY,
+    game_id TEXT NOT NULL REFERENCES games(id)
+);
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    category_id TEXT NOT NULL REFERENCES categories(id),
+    taken_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    run_id TEXT NOT NULL,
+    time_seconds REAL NOT NULL,
+    player TEXT NOT NULL,
+    submitted TEXT NOT NULL,
+    weblink TEXT NOT NULL
+);
+";
+
+pub(crate) struct HistoryStore {
+    conn: Connection,
+}
+
+/// A world record held at a point in time, as recorded by a snapshot.
+#[derive(Debug, Clone)]
+pub struct WrChange {
+    pub player: String,
+    pub time_seconds: f64,
+    pub taken_at: u64,
+}
+
+/// A run present in the latest snapshot but absent from the one before it.
+#[derive(Debug, Clone)]
+pub struct NewRun {
+    pub run_id: String,
+    pub player: String,
+    pub time_seconds: f64,
+    pub weblink: String,
+}
+
+impl HistoryStore {
+    pub(crate) fn open(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(HistoryStore { conn })
+    }
+
+    /// Persists a snapshot of `record_category`'s current runs.
+    pub(crate) fn record_snapshot(&self, record_category: &RecordCategory) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO games (id) VALUES (?1)",
+            params![record_category.game],
+        )?;
+        self.conn.execute(
+            "INSERT OR IGNORE INTO categories (id, game_id) VALUES (?1, ?2)",
+            params![record_category.category, record_category.game],
+        )?;
+        self.conn.execute(
+            "INSERT INTO snapshots (category_id, taken_at) VALUES (?1, ?2)",
+            params![record_category.category, now_secs()],
+        )?;
+        let snapshot_id = self.conn.last_insert_rowid();
+
+        for run in &record_category.runs {
+            self.conn.execute(
+                "INSERT INTO runs (snapshot_id, run_id, time_seconds, player, submitted, weblink)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    snapshot_id,
+                    run.id,
+                    run.total_seconds(),
+                    run.player_label(),
+                    run.submitted,
+                    run.weblink
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the world-record progression for `category_id`: who held WR,
+    /// what time, and when it changed, across every stored snapshot.
+    pub(crate) fn wr_progression(&self, category_id: &str) -> rusqlite::Result<Vec<WrChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.taken_at, r.player, MIN(r.time_seconds) AS wr_time
+             FROM snapshots s
+             JOIN runs r ON r.snapshot_id = s.id
+             WHERE s.category_id = ?1
+             GROUP BY s.id
+             ORDER BY s.taken_at ASC",
+        )?;
+
+        let snapshots = stmt
+            .query_map(params![category_id], |row| {
+                Ok(WrChange {
+                    taken_at: row.get(0)?,
+                    player: row.get(1)?,
+                    time_seconds: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut progression: Vec<WrChange> = Vec::new();
+        for change in snapshots {
+            let improved = progression
+                .last()
+                .is_none_or(|last| change.time_seconds < last.time_seconds);
+            if improved {
+                progression.push(change);
+            }
+        }
+
+        Ok(progression)
+    }
+
+    /// Returns runs present in the most recent snapshot but absent from the one before it.
+    pub(crate) fn new_runs_since_last_snapshot(
+        &self,
+        category_id: &str,
+    ) -> rusqlite::Result<Vec<NewRun>> {
+        let mut snapshot_ids_stmt = self.conn.prepare(
+            "SELECT id FROM snapshots WHERE category_id = ?1 ORDER BY taken_at DESC LIMIT 2",
+        )?;
+        let snapshot_ids = snapshot_ids_stmt
+            .query_map(params![category_id], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let (latest, previous) = match (snapshot_ids.first(), snapshot_ids.get(1)) {
+            (Some(latest), Some(previous)) => (*latest, *previous),
+            _ => return Ok(Vec::new()),
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT run_id, player, time_seconds, weblink FROM runs
+             WHERE snapshot_id = ?1
+             AND run_id NOT IN (SELECT run_id FROM runs WHERE snapshot_id = ?2)",
+        )?;
+
+        let new_runs = stmt
+            .query_map(params![latest, previous], |row| {
+                Ok(NewRun {
+                    run_id: row.get(0)?,
+                    player: row.get(1)?,
+                    time_seconds: row.get(2)?,
+                    weblink: row.get(3)?,
+                })
+            })?
+            .collect();
+        new_runs
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Default history DB location: `<platform data dir>/speedrun-rust-cli/history.sqlite3`.
+pub(crate) fn default_history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("speedrun-rust-cli").join("history.sqlite3"))
+}
+
+#[cfg(test)]
+fn test_run(id: &str, player: &str, seconds: f64) -> crate::Run {
+    crate::Run {
+        id: id.to_string(),
+        weblink: format!("https://speedrun.com/run/{}", id),
+        video: String::new(),
+        time: iso8601::Duration::YMDHMS {
+            year: 0,
+            month: 0,
+            day: 0,
+            hour: (seconds / 3600.0) as u32,
+            minute: ((seconds % 3600.0) / 60.0) as u32,
+            second: (seconds % 60.0) as u32,
+            millisecond: 0,
+        },
+        submitted: "2024-01-01T00:00:00Z".to_string(),
+        player_refs: vec![crate::PlayerRef {
+            rel: "user".to_string(),
+            id: Some(player.to_string()),
+            name: None,
+            uri: String::new(),
+        }],
+        #[cfg(feature = "twitch")]
+        video_info: None,
+    }
+}
+
+/// Overwrites the most recently inserted snapshot's `taken_at`, so tests
+/// don't depend on two `record_snapshot` calls landing in different seconds.
+#[cfg(test)]
+fn set_latest_taken_at(store: &HistoryStore, taken_at: u64) {
+    store
+        .conn
+        .execute(
+            "UPDATE snapshots SET taken_at = ?1 WHERE id = (SELECT MAX(id) FROM snapshots)",
+            params![taken_at],
+        )
+        .unwrap();
+}
+
+#[test]
+fn wr_progression_and_new_runs_across_snapshots() {
+    let store = HistoryStore::open(Path::new(":memory:")).unwrap();
+
+    let first = RecordCategory {
+        game: "g1".to_string(),
+        weblink: String::new(),
+        category: "c1".to_string(),
+        runs: vec![test_run("r1", "alice", 90.0), test_run("r2", "bob", 95.0)],
+    };
+    store.record_snapshot(&first).unwrap();
+    set_latest_taken_at(&store, 1_000);
+
+    let second = RecordCategory {
+        game: "g1".to_string(),
+        weblink: String::new(),
+        category: "c1".to_string(),
+        runs: vec![
+            test_run("r1", "alice", 90.0),
+            test_run("r2", "bob", 95.0),
+            test_run("r3", "carol", 80.0),
+        ],
+    };
+    store.record_snapshot(&second).unwrap();
+    set_latest_taken_at(&store, 2_000);
+
+    let progression = store.wr_progression("c1").unwrap();
+    assert_eq!(progression.len(), 2);
+    assert_eq!(progression[0].player, "alice");
+    assert_eq!(progression[0].time_seconds, 90.0);
+    assert_eq!(progression[0].taken_at, 1_000);
+    assert_eq!(progression[1].player, "carol");
+    assert_eq!(progression[1].time_seconds, 80.0);
+    assert_eq!(progression[1].taken_at, 2_000);
+
+    let new_runs = store.new_runs_since_last_snapshot("c1").unwrap();
+    assert_eq!(new_runs.len(), 1);
+    assert_eq!(new_runs[0].run_id, "r3");
+    assert_eq!(new_runs[0].player, "carol");
+}
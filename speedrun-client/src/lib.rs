@@ -0,0 +1,641 @@
+//! A small async client for the [speedrun.com API](https://github.com/speedruncomorg/api).
+//!
+//! `SpeedrunClient` owns a single `reqwest::Client` and exposes the typed
+//! lookups the CLI needs (`games`, `game_records`, `categories`, `user`) so
+//! other programs can embed speedrun.com lookups without shelling out.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use urlencoding::encode;
+
+#[macro_use(row)]
+extern crate tabular;
+
+mod cache;
+#[cfg(feature = "history")]
+mod history;
+#[cfg(feature = "report-yaml")]
+mod report;
+mod retry;
+#[cfg(feature = "twitch")]
+mod twitch;
+
+use cache::ResponseCache;
+use retry::RetryConfig;
+use std::time::Duration;
+
+#[cfg(feature = "history")]
+use history::HistoryStore;
+#[cfg(feature = "history")]
+pub use history::{NewRun, WrChange};
+#[cfg(feature = "history")]
+use std::path::PathBuf;
+#[cfg(feature = "twitch")]
+pub use twitch::VodMetadata;
+#[cfg(feature = "twitch")]
+use twitch::TwitchVideoResolver;
+
+/// Deserializes `$raw` (a JSON string), reporting the failing field path via
+/// `serde_path_to_error` on error instead of a bare `serde_json` message. With
+/// the `report-yaml` feature enabled, a failure also dumps the raw JSON, path,
+/// and error to a timestamped file under `reports/` as an actionable artifact.
+macro_rules! deserialize_with_path {
+    ($raw:expr) => {{
+        let raw_json = $raw;
+        match serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(raw_json)) {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                #[cfg(feature = "report-yaml")]
+                report::write_report(raw_json, &err);
+                Err(err)
+            }
+        }
+    }};
+}
+
+const DEFAULT_BASE_URL: &str = "https://speedrun.com/api/v1";
+const DEFAULT_USER_AGENT: &str = concat!("speedrun-rust-cli/", env!("CARGO_PKG_VERSION"));
+
+// How long a cached response is considered fresh, per endpoint. Games and
+// categories are essentially static; leaderboards churn more often.
+const GAMES_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+const CATEGORIES_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+const RECORDS_CACHE_TTL_SECS: u64 = 60 * 60;
+const PLAYER_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// The result of [`SpeedrunClient::get_json`], tagged with whether it was
+/// served from the on-disk cache or a real network fetch.
+struct JsonResponse {
+    body: HashMap<String, Value>,
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    from_cache: bool,
+}
+
+/// An async client for the speedrun.com API.
+///
+/// Build one with [`SpeedrunClient::new`] or [`SpeedrunClient::builder`] if
+/// you need a custom base URL, user agent, or API token.
+pub struct SpeedrunClient {
+    http_client: reqwest::Client,
+    base_url: String,
+    api_token: Option<String>,
+    cache: Option<Mutex<ResponseCache>>,
+    refresh: bool,
+    retry_config: RetryConfig,
+    #[cfg(feature = "history")]
+    history: Option<Mutex<HistoryStore>>,
+    #[cfg(feature = "twitch")]
+    twitch: Option<TwitchVideoResolver>,
+}
+
+impl SpeedrunClient {
+    /// Creates a client with the default speedrun.com base URL and no API token.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Starts building a client with a custom base URL, user agent, or API token.
+    pub fn builder() -> SpeedrunClientBuilder {
+        SpeedrunClientBuilder::default()
+    }
+
+    fn request(&self, uri: &str) -> reqwest::RequestBuilder {
+        let req = self.http_client.get(uri);
+        match &self.api_token {
+            Some(token) => req.header("X-API-Key", token),
+            None => req,
+        }
+    }
+
+    /// Fetches `uri` as JSON, consulting the on-disk cache first (unless
+    /// disabled or `--refresh` was requested) and populating it on a miss.
+    async fn get_json(&self, uri: &str, ttl_secs: u64) -> Result<JsonResponse, Box<dyn std::error::Error>> {
+        if let Some(cache) = &self.cache {
+            if !self.refresh {
+                if let Some(cached) = cache.lock().unwrap().get(uri) {
+                    return Ok(JsonResponse {
+                        body: serde_json::from_value(cached)?,
+                        from_cache: true,
+                    });
+                }
+            }
+        }
+
+        let resp = retry::send_with_retry(self.request(uri), &self.retry_config)
+            .await?
+            .json::<HashMap<String, Value>>()
+            .await?;
+
+        if let Some(cache) = &self.cache {
+            cache
+                .lock()
+                .unwrap()
+                .put(uri.to_string(), serde_json::to_value(&resp)?, ttl_secs);
+        }
+
+        Ok(JsonResponse {
+            body: resp,
+            from_cache: false,
+        })
+    }
+
+    /// Searches for games by (partial) name.
+    pub async fn games(&self, name: &str) -> Result<Vec<GameResult>, Box<dyn std::error::Error>> {
+        let uri = format!("{}/games?name={}", self.base_url, encode(name));
+        let resp = self.get_json(&uri, GAMES_CACHE_TTL_SECS).await?;
+
+        Ok(deserialize_with_path!(&resp.body["data"].to_string())?)
+    }
+
+    /// Fetches the record categories (and their runs) behind a game's `records` link.
+    pub async fn game_records(
+        &self,
+        uri: &str,
+    ) -> Result<Vec<RecordCategory>, Box<dyn std::error::Error>> {
+        let records_resp = self.get_json(uri, RECORDS_CACHE_TTL_SECS).await?;
+        let records: Vec<RecordCategory> =
+            deserialize_with_path!(&records_resp.body["data"].to_string())?;
+
+        #[cfg(feature = "history")]
+        if !records_resp.from_cache {
+            if let Some(history) = &self.history {
+                let store = history.lock().unwrap();
+                for record in &records {
+                    if let Err(err) = store.record_snapshot(record) {
+                        eprintln!("warning: failed to record leaderboard snapshot: {}", err);
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "twitch")]
+        let mut records = records;
+        #[cfg(feature = "twitch")]
+        if let Some(twitch) = &self.twitch {
+            let video_urls: Vec<String> = records
+                .iter()
+                .flat_map(|record| record.runs.iter())
+                .map(|run| run.video.clone())
+                .filter(|video| !video.is_empty())
+                .collect();
+
+            match twitch.resolve(&video_urls).await {
+                Ok(mut metadata_by_url) => {
+                    for run in records.iter_mut().flat_map(|record| record.runs.iter_mut()) {
+                        run.video_info = metadata_by_url.remove(&run.video);
+                    }
+                }
+                Err(err) => eprintln!("warning: failed to resolve twitch video metadata: {}", err),
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Returns the world-record progression for `category_id`: who held WR,
+    /// what time, and when it changed, across every stored snapshot.
+    #[cfg(feature = "history")]
+    pub fn wr_progression(&self, category_id: &str) -> Result<Vec<WrChange>, Box<dyn std::error::Error>> {
+        let history = self
+            .history
+            .as_ref()
+            .ok_or("history tracking is not enabled on this client")?;
+        Ok(history.lock().unwrap().wr_progression(category_id)?)
+    }
+
+    /// Returns runs newly submitted since the last stored snapshot of `category_id`.
+    #[cfg(feature = "history")]
+    pub fn new_runs_since_last_snapshot(
+        &self,
+        category_id: &str,
+    ) -> Result<Vec<NewRun>, Box<dyn std::error::Error>> {
+        let history = self
+            .history
+            .as_ref()
+            .ok_or("history tracking is not enabled on this client")?;
+        Ok(history.lock().unwrap().new_runs_since_last_snapshot(category_id)?)
+    }
+
+    /// Fetches a game's categories, keyed by category id.
+    pub async fn categories(
+        &self,
+        uri: &str,
+    ) -> Result<HashMap<String, CategoryObj>, Box<dyn std::error::Error>> {
+        let categories_resp = self.get_json(uri, CATEGORIES_CACHE_TTL_SECS).await?;
+
+        let categories: Vec<CategoryObj> =
+            deserialize_with_path!(&categories_resp.body["data"].to_string())?;
+
+        let mut hash = HashMap::new();
+
+        for cat in categories {
+            hash.insert(
+                cat.id.clone(),
+                CategoryObj {
+                    id: cat.id.clone(),
+                    name: cat.name,
+                    r#type: cat.r#type,
+                },
+            );
+        }
+
+        Ok(hash)
+    }
+
+    /// Fetches a player/user by their speedrun.com id.
+    pub async fn user(&self, id: &str) -> Result<Player, Box<dyn std::error::Error>> {
+        let uri = format!("{}/users/{}", self.base_url, id);
+        let player_resp = self.get_json(&uri, PLAYER_CACHE_TTL_SECS).await?;
+
+        let player: Player = deserialize_with_path!(&player_resp.body["data"].to_string())?;
+        Ok(player)
+    }
+}
+
+impl Default for SpeedrunClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for [`SpeedrunClient`].
+#[derive(Default)]
+pub struct SpeedrunClientBuilder {
+    base_url: Option<String>,
+    user_agent: Option<String>,
+    api_token: Option<String>,
+    no_cache: bool,
+    refresh: bool,
+    max_retries: Option<u32>,
+    base_delay: Option<Duration>,
+    timeout: Option<Duration>,
+    #[cfg(feature = "history")]
+    history_db: Option<PathBuf>,
+    #[cfg(feature = "twitch")]
+    twitch_credentials: Option<(String, String)>,
+}
+
+impl SpeedrunClientBuilder {
+    /// Overrides the API base URL, e.g. for pointing at a test server.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Overrides the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets an API token to send as `X-API-Key` for authenticated endpoints.
+    pub fn api_token(mut self, api_token: impl Into<String>) -> Self {
+        self.api_token = Some(api_token.into());
+        self
+    }
+
+    /// Disables the on-disk response cache entirely.
+    pub fn no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    /// Bypasses cached responses (still writing fresh ones back to the cache).
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Overrides how many times a rate-limited or transiently-failing request is retried.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Overrides the starting delay for exponential backoff between retries.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = Some(base_delay);
+        self
+    }
+
+    /// Overrides the per-request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides where the leaderboard snapshot history database is stored.
+    #[cfg(feature = "history")]
+    pub fn history_db(mut self, path: impl Into<PathBuf>) -> Self {
+        self.history_db = Some(path.into());
+        self
+    }
+
+    /// Sets the Twitch application client id/secret used to resolve
+    /// `Run::video` Twitch VOD links to title, duration, and thumbnail via
+    /// the Helix API.
+    #[cfg(feature = "twitch")]
+    pub fn twitch_credentials(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        self.twitch_credentials = Some((client_id.into(), client_secret.into()));
+        self
+    }
+
+    /// Builds the [`SpeedrunClient`].
+    pub fn build(self) -> SpeedrunClient {
+        let user_agent = self.user_agent.unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        let http_client = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .build()
+            .expect("failed to build reqwest client");
+
+        let cache = if self.no_cache {
+            None
+        } else {
+            cache::default_cache_path().map(|path| Mutex::new(ResponseCache::load(path)))
+        };
+
+        let default_retry_config = RetryConfig::default();
+        let retry_config = RetryConfig {
+            max_retries: self.max_retries.unwrap_or(default_retry_config.max_retries),
+            base_delay: self.base_delay.unwrap_or(default_retry_config.base_delay),
+            timeout: self.timeout.unwrap_or(default_retry_config.timeout),
+        };
+
+        #[cfg(feature = "history")]
+        let history = self
+            .history_db
+            .or_else(history::default_history_path)
+            .and_then(|path| match HistoryStore::open(&path) {
+                Ok(store) => Some(Mutex::new(store)),
+                Err(err) => {
+                    eprintln!("warning: failed to open speedrun-rust-cli history db: {}", err);
+                    None
+                }
+            });
+
+        #[cfg(feature = "twitch")]
+        let twitch = self
+            .twitch_credentials
+            .map(|(client_id, client_secret)| TwitchVideoResolver::new(client_id, client_secret));
+
+        SpeedrunClient {
+            http_client,
+            base_url: self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            api_token: self.api_token,
+            cache,
+            refresh: self.refresh,
+            retry_config,
+            #[cfg(feature = "history")]
+            history,
+            #[cfg(feature = "twitch")]
+            twitch,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GameResult {
+    pub abbreviation: String,
+    pub names: Names,
+    pub released: u16,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Link {
+    pub rel: String,
+    pub uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordCategory {
+    pub game: String,
+    pub weblink: String,
+    pub category: String,
+    pub runs: Vec<Run>,
+}
+
+impl fmt::Display for RecordCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(not(feature = "twitch"))]
+        let mut table = tabular::Table::new("{:<}  {:<}  {:<}  {:<}  {:<}");
+        #[cfg(feature = "twitch")]
+        let mut table = tabular::Table::new("{:<}  {:<}  {:<}  {:<}  {:<}  {:<}");
+
+        #[cfg(not(feature = "twitch"))]
+        table.add_row(row!("Place", "Run ID", "Player ID", "Run Video", "Time"));
+        #[cfg(feature = "twitch")]
+        table.add_row(row!("Place", "Run ID", "Player ID", "Run Video", "Time", "Video"));
+
+        for (i, run) in self.runs.iter().enumerate() {
+            let time: String = {
+                if let iso8601::Duration::YMDHMS {
+                    year: _,
+                    month: _,
+                    day: _,
+                    hour,
+                    minute,
+                    second,
+                    millisecond,
+                } = run.time
+                {
+                    format!("{:02}:{:02}:{:02}:{:02}", hour, minute, second, millisecond)
+                } else {
+                    "No time provided".to_string()
+                }
+            };
+
+            #[cfg(not(feature = "twitch"))]
+            table.add_row(row!(i, &run.id, run.player_label(), &run.video, time));
+            #[cfg(feature = "twitch")]
+            table.add_row(row!(i, &run.id, run.player_label(), &run.video, time, video_column(run)));
+        }
+        write!(f, "{}", table)
+    }
+}
+
+/// Renders a run's "video available / length" column: the VOD's resolved
+/// duration if Helix resolved it, otherwise the bare URL (YouTube links,
+/// unresolvable VODs), otherwise empty.
+#[cfg(feature = "twitch")]
+fn video_column(run: &Run) -> String {
+    match &run.video_info {
+        Some(metadata) => format!("available · {}", metadata.duration),
+        None if !run.video.is_empty() => run.video.clone(),
+        None => String::new(),
+    }
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub id: String,
+    pub weblink: String,
+    pub video: String,
+    pub time: iso8601::Duration,
+    pub submitted: String,
+    pub player_refs: Vec<PlayerRef>,
+    /// The `video` link's resolved Twitch VOD metadata, populated by
+    /// [`SpeedrunClient::game_records`] when the `twitch` feature is enabled
+    /// and configured. `None` for unresolved or non-Twitch links.
+    #[cfg(feature = "twitch")]
+    pub video_info: Option<VodMetadata>,
+}
+
+impl Run {
+    /// Converts this run's recorded time to total seconds, for ranking/comparison.
+    pub fn total_seconds(&self) -> f64 {
+        match self.time {
+            iso8601::Duration::YMDHMS {
+                hour,
+                minute,
+                second,
+                millisecond,
+                ..
+            } => hour as f64 * 3600.0 + minute as f64 * 60.0 + second as f64 + millisecond as f64 / 1000.0,
+            iso8601::Duration::Weeks(weeks) => weeks as f64 * 7.0 * 24.0 * 3600.0,
+        }
+    }
+
+    /// Best-effort player label: the linked account's id, the guest name if
+    /// unlinked, or an empty string if neither is present on the first ref.
+    pub fn player_label(&self) -> String {
+        self.player_refs
+            .first()
+            .and_then(|player_ref| {
+                if player_ref.rel == "user" {
+                    player_ref.id.clone()
+                } else {
+                    player_ref.name.clone()
+                }
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Extracts video `uri`s from a run's raw `videos` field, which is either
+/// `null` or `{"links": [{"uri": ...}, ...]}`. Returns an error instead of
+/// panicking if the `links` field is missing or a link entry has no string
+/// `uri`; a `links` value that is not an array yields no links.
+fn parse_video_links(videos: &Value) -> Result<Vec<String>, String> {
+    if videos.is_null() {
+        return Ok(vec![]);
+    }
+
+    let links = videos
+        .get("links")
+        .ok_or("run videos value is missing a \"links\" field")?;
+
+    match links {
+        Value::Array(entries) => entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| "run video link entry is missing a string \"uri\"".to_string())
+            })
+            .collect(),
+        _ => Ok(vec![]),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerRef {
+    pub rel: String,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub uri: String,
+}
+
+// Figured out how to deserialize & flatten deeply nested JSON from Stack Overflow Answer: https://stackoverflow.com/a/48978402
+impl<'de> Deserialize<'de> for Run {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Debug, Deserialize)]
+        struct RunObj {
+            run: _Run,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct _Run {
+            id: String,
+            weblink: String,
+            videos: Vids,
+            times: Times,
+            submitted: String,
+            players: Vec<PlayerRef>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Vids(Value);
+
+        #[derive(Debug, Deserialize)]
+        struct Times {
+            realtime: String,
+        }
+
+        let help = RunObj::deserialize(deserializer)?;
+
+        let videos = parse_video_links(&help.run.videos.0).map_err(serde::de::Error::custom)?;
+
+        let video = if videos.len() == 2 {
+            videos[1].to_owned()
+        } else if videos.len() == 1 {
+            videos[0].to_owned()
+        } else {
+            String::new()
+        };
+
+        let time = iso8601::duration(&help.run.times.realtime).map_err(|err| {
+            serde::de::Error::custom(format!(
+                "invalid run duration {:?}: {}",
+                help.run.times.realtime, err
+            ))
+        })?;
+
+        Ok(Run {
+            id: help.run.id,
+            weblink: help.run.weblink,
+            video,
+            time,
+            submitted: help.run.submitted,
+            player_refs: help.run.players,
+            #[cfg(feature = "twitch")]
+            video_info: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryObj {
+    pub id: String,
+    pub name: String,
+    pub r#type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Player {
+    pub id: String,
+    pub names: Names,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Names {
+    pub international: Value,
+    pub japanese: Value,
+}
+
+#[test]
+fn should_encode_str() {
+    assert_eq!("Hello%20World".to_string(), encode("Hello World"));
+}
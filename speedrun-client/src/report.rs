@@ -0,0 +1,53 @@
+//! Structured deserialize-error reports, behind the `report-yaml` feature.
+//!
+//! When a [`deserialize_with_path!`](crate::deserialize_with_path) call fails,
+//! write the offending raw JSON alongside the `serde_path_to_error` path and
+//! error into a timestamped YAML file under `reports/`, so a field-shape
+//! change at speedrun.com leaves behind an actionable artifact instead of a
+//! stack trace.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize)]
+struct ErrorReport {
+    path: String,
+    error: String,
+    raw: Value,
+}
+
+/// Best-effort: writes `reports/deserialize-error-<timestamp>.yaml` describing
+/// a failed deserialize. Never panics; a failure to write the report is
+/// logged and swallowed so it can't mask the original error.
+pub(crate) fn write_report(raw_json: &str, err: &serde_path_to_error::Error<serde_json::Error>) {
+    let report = ErrorReport {
+        path: err.path().to_string(),
+        error: err.inner().to_string(),
+        raw: serde_json::from_str(raw_json).unwrap_or(Value::Null),
+    };
+
+    let dir = Path::new("reports");
+    if let Err(write_err) = std::fs::create_dir_all(dir) {
+        eprintln!("warning: failed to create reports directory: {}", write_err);
+        return;
+    }
+
+    let file = dir.join(format!("deserialize-error-{}.yaml", now_millis()));
+    match serde_yaml::to_string(&report) {
+        Ok(yaml) => {
+            if let Err(write_err) = std::fs::write(&file, yaml) {
+                eprintln!("warning: failed to write error report {}: {}", file.display(), write_err);
+            }
+        }
+        Err(yaml_err) => eprintln!("warning: failed to render error report as YAML: {}", yaml_err),
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
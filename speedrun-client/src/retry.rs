@@ -0,0 +1,171 @@
+//! Rate-limit-aware retries for the underlying HTTP requests.
+//!
+//! Every request gets a hard timeout. On HTTP 429 the `Retry-After` header
+//! is honored when present; otherwise (and on transient 5xx/connection
+//! errors) we back off exponentially, doubling the delay up to a cap.
+
+use std::fmt;
+use std::time::Duration;
+
+/// The exponential backoff delay never grows past this, regardless of `base_delay`.
+const BACKOFF_CAP: Duration = Duration::from_secs(30);
+
+/// Retry/timeout knobs for [`crate::SpeedrunClient`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryConfig {
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) timeout: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 5,
+            base_delay: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum TransportError {
+    Timeout,
+    Reqwest(reqwest::Error),
+}
+
+impl TransportError {
+    fn is_transient(&self) -> bool {
+        match self {
+            TransportError::Timeout => true,
+            TransportError::Reqwest(err) => err.is_connect() || err.is_timeout(),
+        }
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportError::Timeout => write!(f, "request timed out"),
+            TransportError::Reqwest(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        TransportError::Reqwest(err)
+    }
+}
+
+/// Sends `req` (cloning it for each attempt), retrying on rate limits,
+/// transient 5xx responses, and connection/timeout errors.
+pub(crate) async fn send_with_retry(
+    req: reqwest::RequestBuilder,
+    config: &RetryConfig,
+) -> Result<reqwest::Response, Box<dyn std::error::Error>> {
+    let mut delay = config.base_delay.min(BACKOFF_CAP);
+
+    for attempt in 0..=config.max_retries {
+        let req = req.try_clone().expect("request body must be cloneable");
+
+        let outcome: Result<reqwest::Response, TransportError> =
+            match tokio::time::timeout(config.timeout, req.send()).await {
+                Ok(Ok(resp)) => Ok(resp),
+                Ok(Err(err)) => Err(err.into()),
+                Err(_) => Err(TransportError::Timeout),
+            };
+
+        match outcome {
+            Ok(resp) if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                if attempt == config.max_retries {
+                    return Err(format!(
+                        "rate limited by speedrun.com after {} attempts",
+                        attempt + 1
+                    )
+                    .into());
+                }
+                let wait = retry_after(resp.headers()).unwrap_or(delay);
+                tokio::time::sleep(wait).await;
+                delay = next_backoff(delay);
+            }
+            Ok(resp) if resp.status().is_server_error() => {
+                if attempt == config.max_retries {
+                    return Err(format!(
+                        "speedrun.com returned {} after {} attempts",
+                        resp.status(),
+                        attempt + 1
+                    )
+                    .into());
+                }
+                tokio::time::sleep(delay).await;
+                delay = next_backoff(delay);
+            }
+            Ok(resp) => return Ok(resp),
+            Err(err) if err.is_transient() && attempt < config.max_retries => {
+                tokio::time::sleep(delay).await;
+                delay = next_backoff(delay);
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    unreachable!("loop always returns on its last iteration")
+}
+
+/// Doubles `delay` for the next attempt, never exceeding [`BACKOFF_CAP`].
+fn next_backoff(delay: Duration) -> Duration {
+    (delay * 2).min(BACKOFF_CAP)
+}
+
+/// Parses the `Retry-After` header's value as a whole number of seconds.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[test]
+fn retry_after_parses_seconds_header() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::RETRY_AFTER, "3".parse().unwrap());
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+}
+
+#[test]
+fn retry_after_ignores_non_numeric_header() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::RETRY_AFTER,
+        "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+    );
+    assert_eq!(retry_after(&headers), None);
+}
+
+#[test]
+fn retry_after_missing_header() {
+    let headers = reqwest::header::HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+}
+
+#[test]
+fn backoff_doubles_until_capped() {
+    let mut delay = Duration::from_secs(1);
+    delay = next_backoff(delay);
+    assert_eq!(delay, Duration::from_secs(2));
+    delay = next_backoff(delay);
+    assert_eq!(delay, Duration::from_secs(4));
+}
+
+#[test]
+fn backoff_never_exceeds_cap() {
+    let near_cap = Duration::from_secs(20);
+    assert_eq!(next_backoff(near_cap), BACKOFF_CAP);
+    assert_eq!(next_backoff(BACKOFF_CAP), BACKOFF_CAP);
+}
@@ -0,0 +1,139 @@
+//! Twitch VOD metadata enrichment via the Helix API, behind the `twitch` feature.
+//!
+//! Given a Twitch client id/secret, [`TwitchVideoResolver`] batch-resolves the
+//! Twitch video ids embedded in `Run::video` URLs to their title, duration,
+//! creation date, and thumbnail via Helix `GetVideos`, so
+//! [`crate::RecordCategory`]'s `Display` impl can show a "video available /
+//! length" column instead of a bare URL. Links that aren't Twitch VODs
+//! (YouTube, etc.) are left unresolved.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use twitch_api2::helix::videos::GetVideosRequest;
+use twitch_api2::helix::HelixClient;
+use twitch_api2::twitch_oauth2::{AppAccessToken, ClientId, ClientSecret};
+
+/// A Twitch VOD's title, duration, creation date, and thumbnail, resolved
+/// from a [`crate::Run::video`] URL via Helix `GetVideos`.
+#[derive(Debug, Clone)]
+pub struct VodMetadata {
+    pub title: String,
+    pub duration: String,
+    pub created_at: String,
+    pub thumbnail_url: String,
+}
+
+/// Helix `GetVideos` allows at most 100 ids per request.
+const MAX_IDS_PER_REQUEST: usize = 100;
+
+pub(crate) struct TwitchVideoResolver {
+    client: HelixClient<'static, reqwest::Client>,
+    client_id: ClientId,
+    client_secret: ClientSecret,
+    token: Mutex<Option<AppAccessToken>>,
+}
+
+impl TwitchVideoResolver {
+    pub(crate) fn new(client_id: String, client_secret: String) -> Self {
+        TwitchVideoResolver {
+            client: HelixClient::default(),
+            client_id: ClientId::new(client_id),
+            client_secret: ClientSecret::new(client_secret),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Returns a cached app access token, fetching one on first use.
+    async fn token(&self) -> Result<AppAccessToken, Box<dyn std::error::Error>> {
+        if let Some(token) = self.token.lock().unwrap().clone() {
+            return Ok(token);
+        }
+
+        let token = AppAccessToken::get_app_access_token(
+            self.client.get_client(),
+            self.client_id.clone(),
+            self.client_secret.clone(),
+            vec![],
+        )
+        .await?;
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(token)
+    }
+
+    /// Batch-resolves the Twitch VOD ids parsed out of `video_urls`, keyed by
+    /// the original URL. URLs that aren't Twitch VODs, or that Helix doesn't
+    /// recognize, are simply absent from the result.
+    pub(crate) async fn resolve(
+        &self,
+        video_urls: &[String],
+    ) -> Result<HashMap<String, VodMetadata>, Box<dyn std::error::Error>> {
+        let ids_by_url: Vec<(String, String)> = video_urls
+            .iter()
+            .filter_map(|url| parse_vod_id(url).map(|id| (url.clone(), id)))
+            .collect();
+
+        if ids_by_url.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let token = self.token().await?;
+
+        let mut metadata_by_id = HashMap::new();
+        for chunk in ids_by_url.chunks(MAX_IDS_PER_REQUEST) {
+            let ids = chunk.iter().map(|(_, id)| id.clone().into()).collect();
+            let request = GetVideosRequest::builder().id(ids).build();
+            let videos = self.client.req_get(request, &token).await?.data;
+            for video in videos {
+                metadata_by_id.insert(
+                    video.id.to_string(),
+                    VodMetadata {
+                        title: video.title,
+                        duration: video.duration,
+                        created_at: video.created_at.to_string(),
+                        thumbnail_url: video.thumbnail_url,
+                    },
+                );
+            }
+        }
+
+        Ok(ids_by_url
+            .into_iter()
+            .filter_map(|(url, id)| metadata_by_id.get(&id).cloned().map(|meta| (url, meta)))
+            .collect())
+    }
+}
+
+/// Parses a Twitch VOD id out of a `twitch.tv/videos/<id>` URL. Returns
+/// `None` for any other shape (YouTube links, Twitch clips, channel pages).
+fn parse_vod_id(url: &str) -> Option<String> {
+    let after_videos = url.split("twitch.tv/videos/").nth(1)?;
+    let id: String = after_videos.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+#[test]
+fn parse_vod_id_extracts_id_from_vod_url() {
+    assert_eq!(
+        parse_vod_id("https://www.twitch.tv/videos/1234567890"),
+        Some("1234567890".to_string())
+    );
+}
+
+#[test]
+fn parse_vod_id_stops_at_query_string() {
+    assert_eq!(
+        parse_vod_id("https://www.twitch.tv/videos/1234567890?t=01h02m03s"),
+        Some("1234567890".to_string())
+    );
+}
+
+#[test]
+fn parse_vod_id_rejects_non_vod_links() {
+    assert_eq!(parse_vod_id("https://www.youtube.com/watch?v=abc123"), None);
+    assert_eq!(parse_vod_id("https://clips.twitch.tv/SomeClipName"), None);
+    assert_eq!(parse_vod_id("https://www.twitch.tv/somechannel"), None);
+}